@@ -2,14 +2,22 @@ pub mod azure;
 pub mod http;
 pub mod opcua;
 pub mod ssh;
+pub mod supervisor;
+pub mod ws;
 
 use crate::error::KernelError;
 use crate::{Res, VoidRes};
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::PoisonError;
 use tokio::sync::mpsc::{self, Sender};
-use tokio::task;
+use tokio::task::{self, JoinHandle};
+use tokio_util::sync::CancellationToken;
 
-type ServerId = String;
+pub(crate) type ServerId = String;
 
 #[derive(Debug)]
 pub enum ServerError {
@@ -17,6 +25,16 @@ pub enum ServerError {
     RuntimeError(String),
     ClientError(String),
 }
+impl std::fmt::Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerError::StartError(msg, id) => write!(f, "failed to start server {id}: {msg}"),
+            ServerError::RuntimeError(msg) => write!(f, "server runtime error: {msg}"),
+            ServerError::ClientError(msg) => write!(f, "server client error: {msg}"),
+        }
+    }
+}
+impl std::error::Error for ServerError {}
 impl<T> From<PoisonError<T>> for ServerError {
     fn from(error: PoisonError<T>) -> Self {
         ServerError::RuntimeError(error.to_string())
@@ -28,6 +46,49 @@ impl From<russh::Error> for ServerError {
     }
 }
 
+/// PEM cert chain and private key to present when a server should terminate TLS
+/// itself rather than serve plaintext. Only the servers that opt into this
+/// (currently HTTP and SSH) consult it; other servers ignore it.
+pub struct TlsConfig {
+    pub cert_chain: PathBuf,
+    pub private_key: PathBuf,
+}
+
+impl TlsConfig {
+    pub fn new(cert_chain: impl Into<PathBuf>, private_key: impl Into<PathBuf>) -> Self {
+        TlsConfig {
+            cert_chain: cert_chain.into(),
+            private_key: private_key.into(),
+        }
+    }
+
+    pub fn load(&self) -> Result<Arc<rustls::ServerConfig>, ServerError> {
+        let cert_file = File::open(&self.cert_chain)
+            .map_err(|e| ServerError::StartError(e.to_string(), self.cert_chain.display().to_string()))?;
+        let certs = rustls_pemfile::certs(&mut BufReader::new(cert_file))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ServerError::StartError(e.to_string(), self.cert_chain.display().to_string()))?;
+
+        let key_file = File::open(&self.private_key)
+            .map_err(|e| ServerError::StartError(e.to_string(), self.private_key.display().to_string()))?;
+        let key = rustls_pemfile::private_key(&mut BufReader::new(key_file))
+            .map_err(|e| ServerError::StartError(e.to_string(), self.private_key.display().to_string()))?
+            .ok_or_else(|| {
+                ServerError::StartError(
+                    "no private key found in PEM file".to_string(),
+                    self.private_key.display().to_string(),
+                )
+            })?;
+
+        let config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| ServerError::StartError(e.to_string(), self.cert_chain.display().to_string()))?;
+
+        Ok(Arc::new(config))
+    }
+}
+
 pub struct ServerHandle<Mes> {
     sender: Sender<Mes>,
 }
@@ -40,34 +101,27 @@ impl<Mes> ServerHandle<Mes> {
     pub async fn send(&self, message: Mes) -> VoidRes {
         Ok(self.sender.send(message).await?)
     }
-
-    pub fn send_sync(&self, message: Mes) -> VoidRes {
-        let sender = self.sender.clone();
-        task::block_in_place(move || {
-            sender
-                .blocking_send(message)
-                .map_err(|e| KernelError::ChannelError(e.to_string()))
-        })
-    }
 }
 
+#[async_trait]
 pub trait Server<Mes> {
-    fn start(&mut self) -> VoidRes;
-    fn stop(&mut self) -> VoidRes;
-    fn process(&mut self, message: Mes) -> VoidRes;
+    async fn start(&mut self) -> VoidRes;
+    async fn stop(&mut self) -> VoidRes;
+    async fn process(&mut self, message: Mes) -> VoidRes;
 }
 
 pub fn spawn_server<M, Serv>(
     mut server: Serv,
     err_sender: Option<Sender<KernelError>>,
-) -> Res<ServerHandle<M>>
+    cancellation_token: CancellationToken,
+) -> Res<(ServerHandle<M>, JoinHandle<()>)>
 where
     Serv: Server<M> + Send + 'static,
     M: Send + 'static,
 {
     let (sender, mut receiver) = mpsc::channel::<M>(32);
-    task::spawn(async move {
-        if let Err(e) = server.start() {
+    let join_handle = task::spawn(async move {
+        if let Err(e) = server.start().await {
             if let Some(err_sender) = err_sender {
                 let _ = err_sender.send(e).await;
             }
@@ -76,18 +130,24 @@ where
         loop {
             tokio::select! {
                 Some(message) = receiver.recv() => {
-                    if let Err(e) = server.process(message) {
+                    if let Err(e) = server.process(message).await {
                         if let Some(ref err_sender) = err_sender {
                             let _ = err_sender.send(e).await;
                         }
                     }
                 }
+                _ = cancellation_token.cancelled() => {
+                    let _ = server.stop().await;
+                    receiver.close();
+                    while receiver.recv().await.is_some() {}
+                    break;
+                }
                 else => break,
             }
         }
     });
 
-    Ok(ServerHandle::new(sender))
+    Ok((ServerHandle::new(sender), join_handle))
 }
 
 mod tests {
@@ -95,14 +155,15 @@ mod tests {
     use crate::servers::{ServerError, spawn_server};
     use crate::{VoidRes, init_logger};
     use serde_json::Value;
-    use std::time::Duration;
-    use tokio::time::sleep;
+    use tokio_util::sync::CancellationToken;
 
     #[tokio::test]
     async fn test_http_server() -> VoidRes {
         init_logger();
 
-        let server_handle = spawn_server(BaseHttpServer::default(), None)?;
+        let cancellation_token = CancellationToken::new();
+        let (server_handle, join_handle) =
+            spawn_server(BaseHttpServer::default(), None, cancellation_token.clone())?;
 
         let client = reqwest::Client::new();
         let response = client
@@ -120,9 +181,19 @@ mod tests {
         assert_eq!(body["status"], "up");
 
         server_handle.sender.send(HttpMessage::Stop).await?;
+        cancellation_token.cancel();
 
-        sleep(Duration::from_millis(100)).await;
+        join_handle.await.map_err(|e| ServerError::RuntimeError(e.to_string()))?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_tls_config_load_reports_missing_files() {
+        let tls = crate::servers::TlsConfig::new("/no/such/cert.pem", "/no/such/key.pem");
+
+        let err = tls.load().expect_err("missing cert/key must not load");
+
+        assert!(matches!(err, ServerError::StartError(_, _)));
+    }
 }
@@ -0,0 +1,209 @@
+use crate::servers::{Server, ServerError, TlsConfig};
+use crate::VoidRes;
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::response::sse::{Event, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use futures_util::stream::Stream;
+use hyper_util::rt::TokioIo;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt as _;
+use tower::Service;
+
+#[derive(Debug, Clone)]
+pub enum HttpMessage {
+    Event(serde_json::Value),
+    Stop,
+}
+
+#[derive(Clone)]
+struct AppState {
+    events: broadcast::Sender<HttpMessage>,
+}
+
+pub struct BaseHttpServer {
+    addr: String,
+    events: broadcast::Sender<HttpMessage>,
+    tls: Option<TlsConfig>,
+    shutdown: Option<oneshot::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl BaseHttpServer {
+    pub fn new(addr: impl Into<String>) -> Self {
+        let (events, _) = broadcast::channel(32);
+        BaseHttpServer {
+            addr: addr.into(),
+            events,
+            tls: None,
+            shutdown: None,
+            join_handle: None,
+        }
+    }
+
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+}
+
+impl Default for BaseHttpServer {
+    fn default() -> Self {
+        BaseHttpServer::new("127.0.0.1:8080")
+    }
+}
+
+async fn health() -> impl IntoResponse {
+    Json(json!({ "status": "up" }))
+}
+
+async fn events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, ServerError>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).filter_map(|item| match item {
+        Ok(HttpMessage::Event(value)) => Some(Ok(Event::default().data(value.to_string()))),
+        Ok(HttpMessage::Stop) => None,
+        Err(BroadcastStreamRecvError::Lagged(n)) => Some(Err(ServerError::RuntimeError(
+            format!("SSE client lagged behind by {n} events"),
+        ))),
+    });
+    Sse::new(stream)
+}
+
+#[async_trait]
+impl Server<HttpMessage> for BaseHttpServer {
+    async fn start(&mut self) -> VoidRes {
+        let listener = TcpListener::bind(&self.addr)
+            .await
+            .map_err(|e| ServerError::StartError(e.to_string(), self.addr.clone()))?;
+
+        let state = AppState {
+            events: self.events.clone(),
+        };
+        let app = Router::new()
+            .route("/health", get(health))
+            .route("/events", get(events))
+            .with_state(state);
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        self.shutdown = Some(shutdown_tx);
+
+        match self.tls.take() {
+            None => {
+                self.join_handle = Some(tokio::task::spawn(async move {
+                    let _ = axum::serve(listener, app)
+                        .with_graceful_shutdown(async {
+                            let _ = shutdown_rx.await;
+                        })
+                        .await;
+                }));
+            }
+            Some(tls) => {
+                let acceptor = TlsAcceptor::from(tls.load()?);
+                self.join_handle = Some(tokio::task::spawn(async move {
+                    loop {
+                        tokio::select! {
+                            accepted = listener.accept() => {
+                                let Ok((stream, _)) = accepted else { break };
+                                let acceptor = acceptor.clone();
+                                let app = app.clone();
+                                tokio::task::spawn(async move {
+                                    if let Ok(tls_stream) = acceptor.accept(stream).await {
+                                        let io = TokioIo::new(tls_stream);
+                                        let service = hyper::service::service_fn(move |req| {
+                                            app.clone().call(req)
+                                        });
+                                        let _ = hyper_util::server::conn::auto::Builder::new(
+                                            hyper_util::rt::TokioExecutor::new(),
+                                        )
+                                        .serve_connection(io, service)
+                                        .await;
+                                    }
+                                });
+                            }
+                            _ = &mut shutdown_rx => break,
+                        }
+                    }
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> VoidRes {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, message: HttpMessage) -> VoidRes {
+        match message {
+            HttpMessage::Stop => self.stop().await,
+            event @ HttpMessage::Event(_) => {
+                let _ = self.events.send(event);
+                Ok(())
+            }
+        }
+    }
+}
+
+mod tests {
+    use super::*;
+    use crate::servers::spawn_server;
+    use crate::init_logger;
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use std::time::Duration;
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    async fn test_sse_events_are_pushed_to_subscribers() -> VoidRes {
+        init_logger();
+
+        let cancellation_token = CancellationToken::new();
+        let (handle, join_handle) = spawn_server(
+            BaseHttpServer::new("127.0.0.1:8083"),
+            None,
+            cancellation_token.clone(),
+        )?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let client = reqwest::Client::new();
+        let mut stream = client
+            .get("http://127.0.0.1:8083/events")
+            .send()
+            .await
+            .map_err(|e| ServerError::ClientError(e.to_string()))?
+            .bytes_stream();
+
+        handle
+            .send(HttpMessage::Event(json!({ "temp": 42 })))
+            .await?;
+
+        let chunk = tokio::time::timeout(Duration::from_secs(1), stream.next())
+            .await
+            .map_err(|e| ServerError::ClientError(e.to_string()))?
+            .transpose()
+            .map_err(|e| ServerError::ClientError(e.to_string()))?
+            .unwrap_or_default();
+
+        assert!(String::from_utf8_lossy(&chunk).contains("temp"));
+
+        cancellation_token.cancel();
+        join_handle
+            .await
+            .map_err(|e| ServerError::RuntimeError(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::servers::{Server, ServerError, TlsConfig};
+use crate::VoidRes;
+use async_trait::async_trait;
+use russh::server::{Auth, Config, Msg, Server as RusshServer, Session};
+use russh::{Channel, ChannelId, CryptoVec};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+
+#[derive(Debug, Clone)]
+pub enum SshMessage {
+    Data(Vec<u8>),
+    Stop,
+}
+
+pub struct BaseSshServer {
+    addr: String,
+    tls: Option<TlsConfig>,
+    shutdown: Option<oneshot::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl BaseSshServer {
+    pub fn new(addr: impl Into<String>) -> Self {
+        BaseSshServer {
+            addr: addr.into(),
+            tls: None,
+            shutdown: None,
+            join_handle: None,
+        }
+    }
+
+    /// Wraps the accepted TCP stream in a TLS session before handing it to
+    /// `russh`, i.e. SSH-over-TLS tunneling (as used by devices that sit
+    /// behind TLS-terminating proxies/firewalls), NOT an alternate SSH
+    /// transport negotiated by the protocol itself. Double check this is
+    /// actually what the target device expects before enabling it — most
+    /// SSH clients connect in plaintext and rely on SSH's own key exchange
+    /// for encryption.
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+}
+
+impl Default for BaseSshServer {
+    fn default() -> Self {
+        BaseSshServer::new("127.0.0.1:2222")
+    }
+}
+
+#[async_trait]
+impl Server<SshMessage> for BaseSshServer {
+    async fn start(&mut self) -> VoidRes {
+        let listener = TcpListener::bind(&self.addr)
+            .await
+            .map_err(|e| ServerError::StartError(e.to_string(), self.addr.clone()))?;
+
+        let ssh_config = Arc::new(Config::default());
+        let acceptor = match self.tls.take() {
+            Some(tls) => Some(TlsAcceptor::from(tls.load()?)),
+            None => None,
+        };
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        self.shutdown = Some(shutdown_tx);
+
+        self.join_handle = Some(tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else { break };
+                        let ssh_config = ssh_config.clone();
+                        match acceptor.clone() {
+                            Some(acceptor) => {
+                                tokio::task::spawn(async move {
+                                    if let Ok(tls_stream) = acceptor.accept(stream).await {
+                                        let _ = russh::server::run_stream(
+                                            ssh_config,
+                                            tls_stream,
+                                            SshHandler,
+                                        )
+                                        .await;
+                                    }
+                                });
+                            }
+                            None => {
+                                tokio::task::spawn(async move {
+                                    let _ =
+                                        russh::server::run_stream(ssh_config, stream, SshHandler)
+                                            .await;
+                                });
+                            }
+                        }
+                    }
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> VoidRes {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, message: SshMessage) -> VoidRes {
+        match message {
+            SshMessage::Stop => self.stop().await,
+            SshMessage::Data(_) => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SshHandler;
+
+impl RusshServer for SshHandler {
+    type Handler = Self;
+
+    fn new_client(&mut self, _addr: Option<std::net::SocketAddr>) -> Self {
+        self.clone()
+    }
+}
+
+#[async_trait]
+impl russh::server::Handler for SshHandler {
+    type Error = ServerError;
+
+    async fn auth_password(&mut self, _user: &str, _password: &str) -> Result<Auth, Self::Error> {
+        Ok(Auth::Accept)
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        _channel: Channel<Msg>,
+        _session: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn data(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        session.data(channel, CryptoVec::from(data.to_vec()))?;
+        Ok(())
+    }
+}
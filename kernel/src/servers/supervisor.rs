@@ -0,0 +1,190 @@
+use crate::servers::{spawn_server, Server, ServerError, ServerHandle, ServerId};
+use crate::VoidRes;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerStatus {
+    Up,
+    Down,
+    Error(String),
+}
+
+struct ServerEntry<M> {
+    handle: ServerHandle<M>,
+    cancellation_token: CancellationToken,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+/// Owns a registry of named servers and reports a consolidated health view,
+/// so a twin composed of several protocol servers (HTTP, OPC UA, SSH, Azure)
+/// can be queried as one unit instead of tracking each `ServerHandle` by hand.
+pub struct Supervisor<M> {
+    entries: HashMap<ServerId, ServerEntry<M>>,
+    status: Arc<Mutex<HashMap<ServerId, ServerStatus>>>,
+}
+
+impl<M> Supervisor<M>
+where
+    M: Clone + Send + 'static,
+{
+    pub fn new() -> Self {
+        Supervisor {
+            entries: HashMap::new(),
+            status: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn spawn<Serv>(&mut self, name: impl Into<ServerId>, server: Serv) -> VoidRes
+    where
+        Serv: Server<M> + Send + 'static,
+    {
+        let name = name.into();
+        let (err_sender, mut err_receiver) = mpsc::channel(16);
+        let cancellation_token = CancellationToken::new();
+        let (handle, join_handle) =
+            spawn_server(server, Some(err_sender), cancellation_token.clone())?;
+
+        self.status
+            .lock()
+            .map_err(ServerError::from)?
+            .insert(name.clone(), ServerStatus::Up);
+
+        let status = self.status.clone();
+        let failed = name.clone();
+        tokio::task::spawn(async move {
+            while let Some(error) = err_receiver.recv().await {
+                if let Ok(mut status) = status.lock() {
+                    status.insert(failed.clone(), ServerStatus::Error(error.to_string()));
+                }
+            }
+        });
+
+        self.entries.insert(
+            name,
+            ServerEntry {
+                handle,
+                cancellation_token,
+                join_handle: Some(join_handle),
+            },
+        );
+        Ok(())
+    }
+
+    /// Sends `message` to every registered server. A single server's channel
+    /// being closed doesn't stop the message reaching the rest; failures are
+    /// folded into the last error seen so callers still learn something went
+    /// wrong without losing delivery to healthy servers.
+    pub async fn broadcast(&self, message: M) -> VoidRes {
+        let mut last_err = None;
+        for entry in self.entries.values() {
+            if let Err(e) = entry.handle.send(message.clone()).await {
+                last_err = Some(e);
+            }
+        }
+        match last_err {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Cancels the named server and waits for its task to exit. The entry
+    /// (and its now-closed `ServerHandle`) stays in the registry marked
+    /// `Down`, so a later `broadcast` still attempts delivery to it and
+    /// reports the failure rather than silently skipping it.
+    pub async fn stop(&mut self, name: &str) -> VoidRes {
+        if let Some(entry) = self.entries.get_mut(name) {
+            entry.cancellation_token.cancel();
+            if let Some(join_handle) = entry.join_handle.take() {
+                let _ = join_handle.await;
+            }
+            self.status
+                .lock()
+                .map_err(ServerError::from)?
+                .insert(name.to_string(), ServerStatus::Down);
+        }
+        Ok(())
+    }
+
+    pub fn health(&self) -> Result<HashMap<ServerId, ServerStatus>, ServerError> {
+        Ok(self.status.lock().map_err(ServerError::from)?.clone())
+    }
+}
+
+impl<M> Default for Supervisor<M>
+where
+    M: Clone + Send + 'static,
+{
+    fn default() -> Self {
+        Supervisor::new()
+    }
+}
+
+mod tests {
+    use super::*;
+    use crate::VoidRes;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[derive(Debug, Clone)]
+    enum TestMessage {
+        Ping,
+    }
+
+    struct CountingServer(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl Server<TestMessage> for CountingServer {
+        async fn start(&mut self) -> VoidRes {
+            Ok(())
+        }
+        async fn stop(&mut self) -> VoidRes {
+            Ok(())
+        }
+        async fn process(&mut self, _message: TestMessage) -> VoidRes {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_up_after_spawn() -> VoidRes {
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn("good", CountingServer(Arc::new(AtomicUsize::new(0))))?;
+
+        let health = supervisor.health()?;
+        assert_eq!(health.get("good"), Some(&ServerStatus::Up));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_reaches_remaining_servers_after_one_is_stopped() -> VoidRes {
+        let a_received = Arc::new(AtomicUsize::new(0));
+        let b_received = Arc::new(AtomicUsize::new(0));
+
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn("a", CountingServer(a_received.clone()))?;
+        supervisor.spawn("b", CountingServer(b_received.clone()))?;
+
+        supervisor.stop("a").await?;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // "a"'s channel is gone, but "b" must still receive the broadcast.
+        let result = supervisor.broadcast(TestMessage::Ping).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert!(result.is_err());
+        assert_eq!(a_received.load(Ordering::SeqCst), 0);
+        assert_eq!(b_received.load(Ordering::SeqCst), 1);
+
+        let health = supervisor.health()?;
+        assert_eq!(health.get("a"), Some(&ServerStatus::Down));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::servers::{Server, ServerError};
+use crate::VoidRes;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone)]
+pub enum WsMessage {
+    Text(String),
+    Binary(Vec<u8>),
+    Telemetry(String),
+    Stop,
+}
+
+/// Shared state reachable both from `Server::process` (messages sent through
+/// the `ServerHandle`) and from each connection task (messages decoded off
+/// the wire), so inbound and outbound frames go through the same routing.
+#[derive(Clone)]
+struct WsRuntime {
+    telemetry: broadcast::Sender<WsMessage>,
+    cancellation_token: CancellationToken,
+}
+
+impl WsRuntime {
+    async fn route(&self, message: WsMessage) -> VoidRes {
+        match message {
+            WsMessage::Stop => {
+                self.cancellation_token.cancel();
+                let _ = self.telemetry.send(WsMessage::Stop);
+                Ok(())
+            }
+            other => {
+                let _ = self.telemetry.send(other);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct BaseWsServer {
+    addr: String,
+    runtime: WsRuntime,
+    accept_handle: Option<JoinHandle<()>>,
+}
+
+impl BaseWsServer {
+    pub fn new(addr: impl Into<String>) -> Self {
+        let (telemetry, _) = broadcast::channel(32);
+        BaseWsServer {
+            addr: addr.into(),
+            runtime: WsRuntime {
+                telemetry,
+                cancellation_token: CancellationToken::new(),
+            },
+            accept_handle: None,
+        }
+    }
+}
+
+impl Default for BaseWsServer {
+    fn default() -> Self {
+        BaseWsServer::new("127.0.0.1:8081")
+    }
+}
+
+async fn handle_connection(stream: TcpStream, runtime: WsRuntime) {
+    let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws_stream) => ws_stream,
+        Err(_) => return,
+    };
+    let (mut write, mut read) = ws_stream.split();
+    let mut telemetry_rx = runtime.telemetry.subscribe();
+
+    loop {
+        tokio::select! {
+            Some(Ok(msg)) = read.next() => {
+                let decoded = match msg {
+                    Message::Text(text) => Some(WsMessage::Text(text.to_string())),
+                    Message::Binary(data) => Some(WsMessage::Binary(data.to_vec())),
+                    Message::Close(_) => break,
+                    _ => None,
+                };
+                if let Some(message) = decoded {
+                    let _ = runtime.route(message).await;
+                }
+            }
+            Ok(message) = telemetry_rx.recv() => {
+                let frame = match message {
+                    WsMessage::Text(text) | WsMessage::Telemetry(text) => Message::Text(text.into()),
+                    WsMessage::Binary(data) => Message::Binary(data.into()),
+                    WsMessage::Stop => break,
+                };
+                if write.send(frame).await.is_err() {
+                    break;
+                }
+            }
+            else => break,
+        }
+    }
+}
+
+#[async_trait]
+impl Server<WsMessage> for BaseWsServer {
+    async fn start(&mut self) -> VoidRes {
+        let listener = TcpListener::bind(&self.addr)
+            .await
+            .map_err(|e| ServerError::StartError(e.to_string(), self.addr.clone()))?;
+        let runtime = self.runtime.clone();
+
+        self.accept_handle = Some(tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else { break };
+                        tokio::task::spawn(handle_connection(stream, runtime.clone()));
+                    }
+                    _ = runtime.cancellation_token.cancelled() => break,
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> VoidRes {
+        self.runtime.route(WsMessage::Stop).await?;
+        if let Some(handle) = self.accept_handle.take() {
+            handle.abort();
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, message: WsMessage) -> VoidRes {
+        self.runtime.route(message).await
+    }
+}
+
+mod tests {
+    use super::*;
+    use crate::servers::spawn_server;
+    use crate::init_logger;
+    use futures_util::{SinkExt, StreamExt};
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message;
+
+    #[tokio::test]
+    async fn test_inbound_frame_is_routed_to_subscribers() -> VoidRes {
+        init_logger();
+
+        let cancellation_token = CancellationToken::new();
+        let (_handle, join_handle) = spawn_server(
+            BaseWsServer::new("127.0.0.1:8082"),
+            None,
+            cancellation_token.clone(),
+        )?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let (mut ws, _) = tokio_tungstenite::connect_async("ws://127.0.0.1:8082")
+            .await
+            .map_err(|e| ServerError::ClientError(e.to_string()))?;
+
+        ws.send(Message::Text("ping".into()))
+            .await
+            .map_err(|e| ServerError::ClientError(e.to_string()))?;
+
+        let echoed = tokio::time::timeout(Duration::from_secs(1), ws.next())
+            .await
+            .map_err(|e| ServerError::ClientError(e.to_string()))?
+            .transpose()
+            .map_err(|e| ServerError::ClientError(e.to_string()))?;
+
+        assert_eq!(echoed, Some(Message::Text("ping".into())));
+
+        cancellation_token.cancel();
+        join_handle
+            .await
+            .map_err(|e| ServerError::RuntimeError(e.to_string()))?;
+
+        Ok(())
+    }
+}